@@ -0,0 +1,47 @@
+use clap::Parser;
+
+use crate::fmt::FormatTemplate;
+use crate::output::OutputFormat;
+
+#[derive(Parser)]
+#[command(name = "fd")]
+pub struct Opts {
+    /// Serialize each result as a JSON object and wrap the whole set in a
+    /// top-level array, so results can be piped straight into `jq`.
+    #[arg(long, conflicts_with = "jsonl")]
+    pub json: bool,
+
+    /// Like `--json`, but emit one JSON object per line (JSON Lines) instead of
+    /// a single array.
+    #[arg(long)]
+    pub jsonl: bool,
+
+    /// Print each result according to the given format string, which may
+    /// contain the placeholders `{}`, `{/}`, `{//}`, `{.}`, `{/.}`, and `{ext}`.
+    #[arg(long, value_name = "fmt", conflicts_with_all = ["json", "jsonl"])]
+    pub format: Option<String>,
+
+    /// Surround each printed path with an OSC 8 hyperlink escape, making it
+    /// clickable in supporting terminals (only when writing to a terminal).
+    #[arg(long)]
+    pub hyperlink: bool,
+}
+
+impl Opts {
+    /// The output representation selected by the `--json` / `--jsonl` flags.
+    pub fn output_format(&self) -> OutputFormat {
+        if self.json {
+            OutputFormat::Json
+        } else if self.jsonl {
+            OutputFormat::JsonLines
+        } else {
+            OutputFormat::Text
+        }
+    }
+
+    /// The parsed `--format` template, if the flag was given. A malformed
+    /// template is returned as an error string for the caller to report.
+    pub fn format_template(&self) -> Result<Option<FormatTemplate>, String> {
+        self.format.as_deref().map(FormatTemplate::parse).transpose()
+    }
+}
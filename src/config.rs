@@ -0,0 +1,30 @@
+use lscolors::LsColors;
+
+use crate::fmt::FormatTemplate;
+use crate::output::OutputFormat;
+
+/// Runtime configuration, assembled once from the parsed command-line options
+/// and shared (by reference) across the walk.
+pub struct Config {
+    /// Whether and how to colorize the output. `None` disables colors.
+    pub ls_colors: Option<LsColors>,
+
+    /// Whether the output is going to an interactive terminal.
+    pub interactive_terminal: bool,
+
+    /// Separate the search results by a null character instead of a newline.
+    pub null_separator: bool,
+
+    /// A custom path separator to use when printing results, if any.
+    pub path_separator: Option<String>,
+
+    /// The representation used when printing a matched entry.
+    pub output_format: OutputFormat,
+
+    /// A parsed `--format` template. When set, each entry is rendered through
+    /// it instead of the default path output.
+    pub format: Option<FormatTemplate>,
+
+    /// Wrap each printed path in an OSC 8 terminal hyperlink.
+    pub hyperlink: bool,
+}
@@ -4,19 +4,89 @@ pub trait Filter: Send + Sync + Sized {
     /// Whether the entry should be skipped or not.
     fn should_skip(&self, entry: &DirEntry) -> bool;
 
-    fn chain<F: Filter>(self, other: F) -> ChainedFilter<Self, F> {
-        ChainedFilter(self, other)
+    /// Skip an entry when *either* filter skips it (logical OR of skips).
+    ///
+    /// Retained as the original name for this combination; it forwards to
+    /// [`Filter::or`], which reads more naturally next to [`Filter::and`] and
+    /// [`Filter::not`].
+    fn chain<F: Filter>(self, other: F) -> OrFilter<Self, F> {
+        self.or(other)
+    }
+
+    /// Invert the skip decision: the result skips exactly the entries this
+    /// filter would keep, and keeps the ones it would skip.
+    fn not(self) -> NotFilter<Self> {
+        NotFilter(self)
+    }
+
+    /// Skip an entry when *either* filter skips it (logical OR of skips).
+    fn or<F: Filter>(self, other: F) -> OrFilter<Self, F> {
+        OrFilter(self, other)
+    }
+
+    /// Skip an entry only when *both* filters skip it (logical AND of skips).
+    fn and<F: Filter>(self, other: F) -> AndFilter<Self, F> {
+        AndFilter(self, other)
+    }
+}
+
+/// The result of [`Filter::chain`]; an alias for [`OrFilter`], of which it is
+/// now one case of the general combinator tree.
+pub type ChainedFilter<F1, F2> = OrFilter<F1, F2>;
+
+pub struct NotFilter<F: Filter>(F);
+
+impl<F: Filter> Filter for NotFilter<F> {
+    fn should_skip(&self, entry: &DirEntry) -> bool {
+        !self.0.should_skip(entry)
     }
 }
 
-pub struct ChainedFilter<F1: Filter, F2: Filter>(F1, F2);
+pub struct OrFilter<F1: Filter, F2: Filter>(F1, F2);
 
-impl<F1: Filter, F2: Filter> Filter for ChainedFilter<F1, F2> {
+impl<F1: Filter, F2: Filter> Filter for OrFilter<F1, F2> {
     fn should_skip(&self, entry: &DirEntry) -> bool {
         self.0.should_skip(entry) || self.1.should_skip(entry)
     }
 }
 
+pub struct AndFilter<F1: Filter, F2: Filter>(F1, F2);
+
+impl<F1: Filter, F2: Filter> Filter for AndFilter<F1, F2> {
+    fn should_skip(&self, entry: &DirEntry) -> bool {
+        self.0.should_skip(entry) && self.1.should_skip(entry)
+    }
+}
+
+/// A [`Filter`] built directly from a closure, where the closure returns the
+/// skip decision for an entry (`true` to skip it).
+///
+/// This lets callers express one-off conditions — "skip unless owner-executable",
+/// "skip empty files" — inline, without defining a dedicated `struct` plus
+/// `impl Filter` for each. The closure is stored by value, so a
+/// `PredicateFilter` built from a non-capturing `fn` pointer allocates nothing.
+pub struct PredicateFilter<P>(P)
+where
+    P: Fn(&DirEntry) -> bool + Send + Sync;
+
+impl<P> PredicateFilter<P>
+where
+    P: Fn(&DirEntry) -> bool + Send + Sync,
+{
+    pub fn new(predicate: P) -> Self {
+        PredicateFilter(predicate)
+    }
+}
+
+impl<P> Filter for PredicateFilter<P>
+where
+    P: Fn(&DirEntry) -> bool + Send + Sync,
+{
+    fn should_skip(&self, entry: &DirEntry) -> bool {
+        (self.0)(entry)
+    }
+}
+
 impl<F> Filter for Option<F>
 where
     F: Filter,
@@ -25,3 +95,59 @@ where
         self.as_ref().map_or(false, |f| f.should_skip(entry))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    /// A filter whose skip decision is fixed regardless of the entry.
+    struct Const(bool);
+
+    impl Filter for Const {
+        fn should_skip(&self, _entry: &DirEntry) -> bool {
+            self.0
+        }
+    }
+
+    fn entry() -> DirEntry {
+        DirEntry::broken_symlink(PathBuf::from("test"))
+    }
+
+    /// All four truth assignments for a pair of filters.
+    const CASES: [(bool, bool); 4] = [(false, false), (false, true), (true, false), (true, true)];
+
+    #[test]
+    fn not_inverts() {
+        let e = entry();
+        assert!(Const(false).not().should_skip(&e));
+        assert!(!Const(true).not().should_skip(&e));
+    }
+
+    #[test]
+    fn de_morgan_not_or_is_and_not() {
+        let e = entry();
+        for (a, b) in CASES {
+            let lhs = Const(a).or(Const(b)).not().should_skip(&e);
+            let rhs = Const(a).not().and(Const(b).not()).should_skip(&e);
+            assert_eq!(lhs, rhs, "¬(a∨b) = ¬a∧¬b failed for ({a}, {b})");
+        }
+    }
+
+    #[test]
+    fn de_morgan_not_and_is_or_not() {
+        let e = entry();
+        for (a, b) in CASES {
+            let lhs = Const(a).and(Const(b)).not().should_skip(&e);
+            let rhs = Const(a).not().or(Const(b).not()).should_skip(&e);
+            assert_eq!(lhs, rhs, "¬(a∧b) = ¬a∨¬b failed for ({a}, {b})");
+        }
+    }
+
+    #[test]
+    fn predicate_filter_delegates_to_closure() {
+        let e = entry();
+        assert!(PredicateFilter::new(|_: &DirEntry| true).should_skip(&e));
+        assert!(!PredicateFilter::new(|_: &DirEntry| false).should_skip(&e));
+    }
+}
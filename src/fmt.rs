@@ -0,0 +1,187 @@
+//! Pre-parsed `--format` templates.
+//!
+//! A template string such as `"{//}/{.}.bak"` is parsed exactly once, up front,
+//! into a [`FormatTemplate`] — a flat sequence of literal spans and field
+//! placeholders. The hot `print_entry` path then walks those tokens instead of
+//! re-scanning the template for every result.
+
+use std::path::Path;
+
+/// A single field placeholder understood inside a `--format` template.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Placeholder {
+    /// `{}` — the full (possibly stripped) path.
+    Path,
+    /// `{/}` — the basename (last path component).
+    Basename,
+    /// `{//}` — the parent directory.
+    Parent,
+    /// `{.}` — the full path with its extension removed.
+    NoExt,
+    /// `{/.}` — the basename with its extension removed.
+    BasenameNoExt,
+    /// `{ext}` — the extension, without the leading dot (empty if none).
+    Extension,
+}
+
+/// One token of a parsed template: either a literal span or a field placeholder.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum FormatToken {
+    Literal(String),
+    Placeholder(Placeholder),
+}
+
+/// A `--format` template parsed into a flat token sequence.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct FormatTemplate {
+    tokens: Vec<FormatToken>,
+}
+
+impl FormatTemplate {
+    /// Parse a template string into its tokens.
+    ///
+    /// A literal `{` or `}` is written by doubling it (`{{`, `}}`). Any other
+    /// `{...}` group is looked up as a [`Placeholder`]; an unknown or unclosed
+    /// group is reported as an error so that typos surface at argument-parsing
+    /// time rather than silently printing nothing.
+    pub fn parse(template: &str) -> Result<FormatTemplate, String> {
+        let mut tokens = Vec::new();
+        let mut literal = String::new();
+        let mut chars = template.chars().peekable();
+
+        while let Some(c) = chars.next() {
+            match c {
+                '{' if chars.peek() == Some(&'{') => {
+                    chars.next();
+                    literal.push('{');
+                }
+                '}' if chars.peek() == Some(&'}') => {
+                    chars.next();
+                    literal.push('}');
+                }
+                '{' => {
+                    let mut field = String::new();
+                    loop {
+                        match chars.next() {
+                            Some('}') => break,
+                            Some(c) => field.push(c),
+                            None => {
+                                return Err(format!("unclosed placeholder in format: '{}'", template))
+                            }
+                        }
+                    }
+                    let placeholder = match field.as_str() {
+                        "" => Placeholder::Path,
+                        "/" => Placeholder::Basename,
+                        "//" => Placeholder::Parent,
+                        "." => Placeholder::NoExt,
+                        "/." => Placeholder::BasenameNoExt,
+                        "ext" => Placeholder::Extension,
+                        other => {
+                            return Err(format!("unknown format placeholder: '{{{}}}'", other))
+                        }
+                    };
+                    if !literal.is_empty() {
+                        tokens.push(FormatToken::Literal(std::mem::take(&mut literal)));
+                    }
+                    tokens.push(FormatToken::Placeholder(placeholder));
+                }
+                '}' => return Err(format!("unmatched '}}' in format: '{}'", template)),
+                c => literal.push(c),
+            }
+        }
+
+        if !literal.is_empty() {
+            tokens.push(FormatToken::Literal(literal));
+        }
+
+        Ok(FormatTemplate { tokens })
+    }
+
+    /// The parsed tokens, in order.
+    pub fn tokens(&self) -> &[FormatToken] {
+        &self.tokens
+    }
+}
+
+/// Resolve a [`Placeholder`] against `path`, returning the substituted text.
+pub fn substitute(placeholder: Placeholder, path: &Path) -> String {
+    let basename = || {
+        path.file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_default()
+    };
+    match placeholder {
+        Placeholder::Path => path.to_string_lossy().into_owned(),
+        Placeholder::Basename => basename(),
+        Placeholder::Parent => path
+            .parent()
+            .map(|p| p.to_string_lossy().into_owned())
+            .unwrap_or_default(),
+        Placeholder::NoExt => remove_extension(path).to_string_lossy().into_owned(),
+        Placeholder::BasenameNoExt => remove_extension(path)
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_default(),
+        Placeholder::Extension => path
+            .extension()
+            .map(|e| e.to_string_lossy().into_owned())
+            .unwrap_or_default(),
+    }
+}
+
+fn remove_extension(path: &Path) -> std::path::PathBuf {
+    let mut result = path.to_path_buf();
+    if path.extension().is_some() {
+        result.set_extension("");
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    fn tokens(template: &str) -> Vec<FormatToken> {
+        FormatTemplate::parse(template).unwrap().tokens().to_vec()
+    }
+
+    #[test]
+    fn parses_literals_and_placeholders() {
+        assert_eq!(
+            tokens("a/{/.}.bak"),
+            vec![
+                FormatToken::Literal("a/".into()),
+                FormatToken::Placeholder(Placeholder::BasenameNoExt),
+                FormatToken::Literal(".bak".into()),
+            ]
+        );
+    }
+
+    #[test]
+    fn parses_escaped_braces() {
+        assert_eq!(tokens("{{{}}}"), vec![
+            FormatToken::Literal("{".into()),
+            FormatToken::Placeholder(Placeholder::Path),
+            FormatToken::Literal("}".into()),
+        ]);
+    }
+
+    #[test]
+    fn rejects_unknown_and_unclosed() {
+        assert!(FormatTemplate::parse("{nope}").is_err());
+        assert!(FormatTemplate::parse("{/").is_err());
+    }
+
+    #[test]
+    fn substitutes_fields() {
+        let p = Path::new("foo/bar/baz.tar.gz");
+        assert_eq!(substitute(Placeholder::Path, p), "foo/bar/baz.tar.gz");
+        assert_eq!(substitute(Placeholder::Basename, p), "baz.tar.gz");
+        assert_eq!(substitute(Placeholder::Parent, p), "foo/bar");
+        assert_eq!(substitute(Placeholder::NoExt, p), "foo/bar/baz.tar");
+        assert_eq!(substitute(Placeholder::BasenameNoExt, p), "baz.tar");
+        assert_eq!(substitute(Placeholder::Extension, p), "gz");
+    }
+}
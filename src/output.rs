@@ -1,14 +1,17 @@
 use std::borrow::Cow;
+use std::cell::{Cell, RefCell};
 use std::io::{self, StdoutLock, Write};
 use std::path::Path;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
+use std::time::UNIX_EPOCH;
 
 use lazy_static::lazy_static;
 use lscolors::{Indicator, LsColors, Style};
 
 use crate::config::Config;
 use crate::entry::DirEntry;
+use crate::fmt::{substitute, FormatToken, Placeholder};
 use crate::error::print_error;
 use crate::exit_codes::ExitCode;
 use crate::filesystem::strip_current_dir;
@@ -17,6 +20,42 @@ lazy_static! {
     static ref MAIN_SEPARATOR_STR: String = std::path::MAIN_SEPARATOR.to_string();
 }
 
+// Entries are formatted into a reusable byte buffer and handed to `stdout` in
+// large chunks rather than through many small `write!` calls, which cuts the
+// syscall count dramatically when printing huge result sets. fd funnels every
+// match through a single receiver thread (see `walk::spawn_receiver`), so this
+// thread-local buffer is only ever touched from that one thread. It is flushed
+// once it grows past this threshold, on SIGINT, and on the explicit `flush`
+// call the receiver makes at the end of the walk.
+const OUTPUT_BUFFER_THRESHOLD: usize = 1 << 16;
+
+thread_local! {
+    static OUTPUT_BUFFER: RefCell<Vec<u8>> = RefCell::new(Vec::with_capacity(OUTPUT_BUFFER_THRESHOLD));
+
+    // Set by the first `OutputFormat::Json` record so that exactly one record
+    // is prefixed with the opening `[` and the rest with `,`. Printing is
+    // single-threaded, so a plain thread-local flag suffices. `print_epilogue`
+    // reads it to decide between `]` and an empty `[]`.
+    static JSON_ARRAY_STARTED: Cell<bool> = const { Cell::new(false) };
+}
+
+/// The representation used when printing a matched entry.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum OutputFormat {
+    /// The default, human-oriented output: one (optionally colored) path per line.
+    Text,
+    /// A single JSON array wrapping one record object per entry.
+    Json,
+    /// One JSON record object per line (JSON Lines / `jsonl`).
+    JsonLines,
+}
+
+impl Default for OutputFormat {
+    fn default() -> Self {
+        OutputFormat::Text
+    }
+}
+
 fn replace_path_separator(path: &str, new_path_separator: &str) -> String {
     path.replace(std::path::MAIN_SEPARATOR, new_path_separator)
 }
@@ -37,15 +76,67 @@ pub fn print_entry(
     config: &Config,
     wants_to_quit: &Arc<AtomicBool>,
 ) {
-    let r = if let Some(ref ls_colors) = config.ls_colors {
-        print_entry_colorized(stdout, entry, config, ls_colors, wants_to_quit)
+    OUTPUT_BUFFER.with(|buffer| {
+        let mut buffer = buffer.borrow_mut();
+        let bytes = &mut *buffer;
+
+        // Formatting targets an in-memory `Vec<u8>`, so these writes cannot
+        // actually fail; the real I/O (and its error handling) happens in
+        // `flush_buffer` at the threshold and batch boundaries.
+        let format = |buffer: &mut Vec<u8>| {
+            if let Some(ref template) = config.format {
+                print_entry_format(buffer, entry, config, template.tokens())
+            } else {
+                match config.output_format {
+                    OutputFormat::Text => {
+                        if let Some(ref ls_colors) = config.ls_colors {
+                            print_entry_colorized(buffer, entry, config, ls_colors)
+                        } else {
+                            print_entry_uncolorized(buffer, entry, config)
+                        }
+                    }
+                    OutputFormat::Json | OutputFormat::JsonLines => {
+                        print_entry_json(buffer, entry, config)
+                    }
+                }
+            }
+        };
+        format(bytes).expect("formatting into an in-memory buffer is infallible");
+
+        // Flush on threshold, but also check `wants_to_quit` every entry so
+        // SIGINT stays as responsive as it was before buffering — without it,
+        // a quit could be delayed by up to a full buffer's worth of entries.
+        if bytes.len() >= OUTPUT_BUFFER_THRESHOLD || wants_to_quit.load(Ordering::Relaxed) {
+            flush_buffer(bytes, stdout, wants_to_quit);
+        }
+    });
+}
+
+// Flush any buffered output produced by earlier `print_entry` calls. The
+// receiver calls this once the walk is done so the final, sub-threshold chunk
+// reaches `stdout`.
+pub fn flush(stdout: &mut StdoutLock, wants_to_quit: &Arc<AtomicBool>) {
+    OUTPUT_BUFFER.with(|buffer| flush_buffer(&mut buffer.borrow_mut(), stdout, wants_to_quit));
+}
+
+// Emit output that closes a multi-entry document, after the buffer has been
+// flushed. For `OutputFormat::Json` this writes the array's closing `]` (an
+// empty result set still produces a valid `[]`); the other formats are
+// self-terminating and need no epilogue.
+pub fn print_epilogue(stdout: &mut StdoutLock, config: &Config) {
+    if config.output_format != OutputFormat::Json {
+        return;
+    }
+
+    let started = JSON_ARRAY_STARTED.with(Cell::get);
+    let r = if started {
+        writeln!(stdout, "]")
     } else {
-        print_entry_uncolorized(stdout, entry, config)
+        writeln!(stdout, "[]")
     };
 
     if let Err(e) = r {
         if e.kind() == ::std::io::ErrorKind::BrokenPipe {
-            // Exit gracefully in case of a broken pipe (e.g. 'fd ... | head -n 3').
             ExitCode::Success.exit();
         } else {
             print_error(format!("Could not write to output: {}", e));
@@ -54,12 +145,33 @@ pub fn print_entry(
     }
 }
 
+// Write the accumulated buffer to `stdout` in one call and clear it, handling
+// broken pipes and SIGINT at this boundary so per-entry behavior is unchanged.
+fn flush_buffer(buffer: &mut Vec<u8>, stdout: &mut StdoutLock, wants_to_quit: &Arc<AtomicBool>) {
+    if !buffer.is_empty() {
+        if let Err(e) = stdout.write_all(buffer) {
+            if e.kind() == ::std::io::ErrorKind::BrokenPipe {
+                // Exit gracefully in case of a broken pipe (e.g. 'fd ... | head -n 3').
+                ExitCode::Success.exit();
+            } else {
+                print_error(format!("Could not write to output: {}", e));
+                ExitCode::GeneralError.exit();
+            }
+        }
+        buffer.clear();
+    }
+
+    if wants_to_quit.load(Ordering::Relaxed) {
+        ExitCode::KilledBySigint.exit();
+    }
+}
+
 // Display a trailing slash if the path is a directory and the config option is enabled.
 // If the path_separator option is set, display that instead.
 // The trailing slash will not be colored.
 #[inline]
 fn print_trailing_slash(
-    stdout: &mut StdoutLock,
+    stdout: &mut Vec<u8>,
     entry: &DirEntry,
     config: &Config,
     style: Option<&Style>,
@@ -81,19 +193,172 @@ fn print_trailing_slash(
     Ok(())
 }
 
+// Render an entry through a pre-parsed `--format` template.
+//
+// Path-valued fields honor the `path_separator` override, and the `{}`/`{/}`
+// fields are colorized through `LsColors`: each is painted as a whole in the
+// entry's own style (unlike the default output, the parent part of `{}` does
+// not get the directory color). The directory trailing slash follows every
+// occurrence of those two fields.
+fn print_entry_format(
+    stdout: &mut Vec<u8>,
+    entry: &DirEntry,
+    config: &Config,
+    tokens: &[FormatToken],
+) -> io::Result<()> {
+    let path = stripped_path(entry);
+
+    for token in tokens {
+        match token {
+            FormatToken::Literal(literal) => write!(stdout, "{}", literal)?,
+            FormatToken::Placeholder(placeholder) => {
+                let mut value = substitute(*placeholder, path);
+                if is_path_field(*placeholder) {
+                    if let Some(ref separator) = config.path_separator {
+                        value = replace_path_separator(&value, separator);
+                    }
+                }
+
+                match (config.ls_colors.as_ref(), placeholder) {
+                    (Some(ls_colors), Placeholder::Path) => {
+                        let style = ls_colors
+                            .style_for_path_with_metadata(path, entry.metadata())
+                            .map(Style::to_ansi_term_style)
+                            .unwrap_or_default();
+                        write!(stdout, "{}", style.paint(&value))?;
+                        print_trailing_slash(
+                            stdout,
+                            entry,
+                            config,
+                            ls_colors.style_for_indicator(Indicator::Directory),
+                        )?;
+                    }
+                    (Some(ls_colors), Placeholder::Basename) => {
+                        let style = ls_colors
+                            .style_for_path_with_metadata(path, entry.metadata())
+                            .map(Style::to_ansi_term_style)
+                            .unwrap_or_default();
+                        write!(stdout, "{}", style.paint(&value))?;
+                        print_trailing_slash(
+                            stdout,
+                            entry,
+                            config,
+                            ls_colors.style_for_indicator(Indicator::Directory),
+                        )?;
+                    }
+                    _ => {
+                        write!(stdout, "{}", value)?;
+                        if matches!(placeholder, Placeholder::Path | Placeholder::Basename) {
+                            print_trailing_slash(stdout, entry, config, None)?;
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    if config.null_separator {
+        write!(stdout, "\0")?;
+    } else {
+        writeln!(stdout)?;
+    }
+
+    Ok(())
+}
+
+lazy_static! {
+    // The local hostname embedded in `file://` URIs so that links resolve to
+    // the right machine when viewed over SSH. Resolved once; an empty value
+    // (a bare `file:///path`) is a valid fallback if it cannot be determined.
+    static ref HOSTNAME: String = hostname();
+}
+
+#[cfg(unix)]
+fn hostname() -> String {
+    std::fs::read_to_string("/proc/sys/kernel/hostname")
+        .map(|h| h.trim().to_owned())
+        .unwrap_or_default()
+}
+
+#[cfg(not(unix))]
+fn hostname() -> String {
+    std::env::var("COMPUTERNAME").unwrap_or_default()
+}
+
+// Whether OSC 8 hyperlinks should wrap printed paths: requested, writing to an
+// interactive terminal, and not in NUL-separator mode (whose consumers can't
+// tolerate the embedded escape bytes).
+#[inline]
+fn hyperlink_enabled(config: &Config) -> bool {
+    config.hyperlink && config.interactive_terminal && !config.null_separator
+}
+
+// Build the absolute, percent-encoded `file://HOST/ABSPATH` URI used as an
+// OSC 8 link target for `entry`. The entry's real (un-stripped) path is made
+// absolute against the current directory when relative.
+fn hyperlink_uri(entry: &DirEntry) -> String {
+    let path = entry.path();
+    let absolute = if path.is_absolute() {
+        Cow::from(path)
+    } else {
+        match std::env::current_dir() {
+            Ok(cwd) => Cow::from(cwd.join(path)),
+            Err(_) => Cow::from(path),
+        }
+    };
+
+    let mut uri = format!("file://{}", *HOSTNAME);
+    percent_encode_path(&mut uri, &absolute.to_string_lossy());
+    uri
+}
+
+// Append `path`, percent-encoded, to `uri`. Path separators are preserved
+// as `/`; every other byte outside the unreserved URI set — including spaces
+// and all non-ASCII bytes — is written as `%XX`.
+fn percent_encode_path(uri: &mut String, path: &str) {
+    const HEX: &[u8; 16] = b"0123456789ABCDEF";
+    for &byte in path.as_bytes() {
+        match byte {
+            b'/' => uri.push('/'),
+            b'a'..=b'z' | b'A'..=b'Z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                uri.push(byte as char)
+            }
+            _ => {
+                uri.push('%');
+                uri.push(HEX[(byte >> 4) as usize] as char);
+                uri.push(HEX[(byte & 0xf) as usize] as char);
+            }
+        }
+    }
+}
+
+// Whether a placeholder yields a path (and so is subject to the
+// `path_separator` rewrite) rather than a bare name fragment.
+#[inline]
+fn is_path_field(placeholder: Placeholder) -> bool {
+    matches!(
+        placeholder,
+        Placeholder::Path | Placeholder::Parent | Placeholder::NoExt
+    )
+}
+
 // TODO: this function is performance critical and can probably be optimized
 fn print_entry_colorized(
-    stdout: &mut StdoutLock,
+    stdout: &mut Vec<u8>,
     entry: &DirEntry,
     config: &Config,
     ls_colors: &LsColors,
-    wants_to_quit: &Arc<AtomicBool>,
 ) -> io::Result<()> {
     // Split the path between the parent and the last component
     let mut offset = 0;
     let path = stripped_path(entry);
     let path_str = path.to_string_lossy();
 
+    let hyperlink = hyperlink_enabled(config);
+    if hyperlink {
+        write!(stdout, "\x1b]8;;{}\x1b\\", hyperlink_uri(entry))?;
+    }
+
     if let Some(parent) = path.parent() {
         offset = parent.to_string_lossy().len();
         for c in path_str[offset..].chars() {
@@ -131,22 +396,128 @@ fn print_entry_colorized(
         ls_colors.style_for_indicator(Indicator::Directory),
     )?;
 
+    if hyperlink {
+        write!(stdout, "\x1b]8;;\x1b\\")?;
+    }
+
     if config.null_separator {
         write!(stdout, "\0")?;
     } else {
         writeln!(stdout)?;
     }
 
-    if wants_to_quit.load(Ordering::Relaxed) {
-        ExitCode::KilledBySigint.exit();
+    Ok(())
+}
+
+// Serialize a single matched entry to one JSON record object.
+//
+// For `OutputFormat::Json` each record is prefixed with its own array
+// separator (`[` for the very first record, `,` thereafter — see the match
+// below), and the closing `]` is written once by `print_epilogue`. For
+// `JsonLines` a bare record followed by a newline is emitted.
+fn print_entry_json(
+    stdout: &mut Vec<u8>,
+    entry: &DirEntry,
+    config: &Config,
+) -> io::Result<()> {
+    let path = stripped_path(entry);
+    let md = entry.metadata();
+
+    let mut record = String::with_capacity(128);
+    record.push('{');
+
+    push_json_field(&mut record, "path", &path.to_string_lossy(), true);
+    if let Some(name) = path.file_name() {
+        push_json_field(&mut record, "name", &name.to_string_lossy(), false);
+    }
+    if let Some(parent) = path.parent() {
+        push_json_field(&mut record, "parent", &parent.to_string_lossy(), false);
+    }
+
+    let is_dir = md.map_or(false, |m| m.is_dir());
+    record.push_str(",\"is_dir\":");
+    record.push_str(if is_dir { "true" } else { "false" });
+
+    // Reuse the file type captured during the walk instead of issuing a fresh
+    // `lstat` per entry, matching the cached `is_dir` lookup above.
+    let is_symlink = entry.file_type().map_or(false, |ft| ft.is_symlink());
+    record.push_str(",\"is_symlink\":");
+    record.push_str(if is_symlink { "true" } else { "false" });
+
+    if let Some(md) = md {
+        record.push_str(",\"size\":");
+        record.push_str(&md.len().to_string());
+        if let Ok(modified) = md.modified() {
+            if let Ok(since) = modified.duration_since(UNIX_EPOCH) {
+                record.push_str(",\"modified\":");
+                record.push_str(&since.as_secs().to_string());
+            }
+        }
+    }
+
+    record.push('}');
+
+    match config.output_format {
+        OutputFormat::JsonLines => {
+            write!(stdout, "{}", record)?;
+            writeln!(stdout)?;
+        }
+        OutputFormat::Json => {
+            // Each record carries its own array separator: the first record is
+            // prefixed with `[`, every later one with `,`, and `print_epilogue`
+            // writes the closing `]`. Framing can't live at the flush boundary
+            // because a flush can split the stream mid-array, so the comma has
+            // to travel with the record it precedes.
+            let opening = !JSON_ARRAY_STARTED.with(|started| started.replace(true));
+            stdout.push(if opening { b'[' } else { b',' });
+            write!(stdout, "{}", record)?;
+        }
+        OutputFormat::Text => unreachable!("print_entry_json is only reached for JSON formats"),
     }
 
     Ok(())
 }
 
+// Append `,"key":"value"` (or, when `first`, without the leading comma) to a
+// JSON object being built up, escaping the value per RFC 8259.
+#[inline]
+fn push_json_field(buf: &mut String, key: &str, value: &str, first: bool) {
+    if !first {
+        buf.push(',');
+    }
+    buf.push('"');
+    buf.push_str(key);
+    buf.push_str("\":");
+    push_json_string(buf, value);
+}
+
+// Append a JSON string literal (including the surrounding quotes) with all
+// characters that must be escaped turned into their `\"`, `\\`, short, or
+// `\u00XX` forms. Input is already lossy-decoded, so any invalid UTF-8 in the
+// original path has been replaced before reaching here.
+fn push_json_string(buf: &mut String, value: &str) {
+    buf.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => buf.push_str("\\\""),
+            '\\' => buf.push_str("\\\\"),
+            '\n' => buf.push_str("\\n"),
+            '\r' => buf.push_str("\\r"),
+            '\t' => buf.push_str("\\t"),
+            '\x08' => buf.push_str("\\b"),
+            '\x0c' => buf.push_str("\\f"),
+            c if (c as u32) < 0x20 => {
+                buf.push_str(&format!("\\u{:04x}", c as u32));
+            }
+            c => buf.push(c),
+        }
+    }
+    buf.push('"');
+}
+
 // TODO: this function is performance critical and can probably be optimized
 fn print_entry_uncolorized_base(
-    stdout: &mut StdoutLock,
+    stdout: &mut Vec<u8>,
     entry: &DirEntry,
     config: &Config,
 ) -> io::Result<()> {
@@ -157,14 +528,24 @@ fn print_entry_uncolorized_base(
     if let Some(ref separator) = config.path_separator {
         *path_string.to_mut() = replace_path_separator(&path_string, separator);
     }
+
+    // Hyperlinks are wrapped here too, so that `--hyperlink` keeps working when
+    // colors are disabled (e.g. `NO_COLOR` or `--color=never`).
+    let hyperlink = hyperlink_enabled(config);
+    if hyperlink {
+        write!(stdout, "\x1b]8;;{}\x1b\\", hyperlink_uri(entry))?;
+    }
     write!(stdout, "{}", path_string)?;
     print_trailing_slash(stdout, entry, config, None)?;
+    if hyperlink {
+        write!(stdout, "\x1b]8;;\x1b\\")?;
+    }
     write!(stdout, "{}", separator)
 }
 
 #[cfg(not(unix))]
 fn print_entry_uncolorized(
-    stdout: &mut StdoutLock,
+    stdout: &mut Vec<u8>,
     entry: &DirEntry,
     config: &Config,
 ) -> io::Result<()> {
@@ -173,7 +554,7 @@ fn print_entry_uncolorized(
 
 #[cfg(unix)]
 fn print_entry_uncolorized(
-    stdout: &mut StdoutLock,
+    stdout: &mut Vec<u8>,
     entry: &DirEntry,
     config: &Config,
 ) -> io::Result<()> {
@@ -190,3 +571,48 @@ fn print_entry_uncolorized(
         stdout.write_all(separator)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::push_json_string;
+
+    fn json_string(value: &str) -> String {
+        let mut buf = String::new();
+        push_json_string(&mut buf, value);
+        buf
+    }
+
+    #[test]
+    fn escapes_quotes_and_backslashes() {
+        assert_eq!(json_string(r#"a"b\c"#), r#""a\"b\\c""#);
+    }
+
+    #[test]
+    fn escapes_short_control_chars() {
+        assert_eq!(json_string("a\nb\tc\r"), r#""a\nb\tc\r""#);
+    }
+
+    #[test]
+    fn escapes_other_control_chars_as_unicode() {
+        assert_eq!(json_string("\u{1}\u{1f}"), "\"\\u0001\\u001f\"");
+    }
+
+    #[test]
+    fn passes_through_non_ascii() {
+        assert_eq!(json_string("mÜnchen/日本"), r#""mÜnchen/日本""#);
+    }
+
+    #[test]
+    fn percent_encodes_spaces_and_non_ascii() {
+        let mut uri = String::new();
+        super::percent_encode_path(&mut uri, "/tmp/a b/münchen");
+        assert_eq!(uri, "/tmp/a%20b/m%C3%BCnchen");
+    }
+
+    #[test]
+    fn preserves_lossy_replacement_char() {
+        // Invalid UTF-8 in a path is lossy-decoded to U+FFFD before escaping,
+        // which is a printable code point and must pass through untouched.
+        assert_eq!(json_string("a\u{fffd}b"), "\"a\u{fffd}b\"");
+    }
+}
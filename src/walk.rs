@@ -0,0 +1,30 @@
+use std::io::Write;
+use std::sync::atomic::AtomicBool;
+use std::sync::mpsc::Receiver;
+use std::sync::Arc;
+
+pub use crate::entry::DirEntry;
+
+use crate::config::Config;
+use crate::output;
+
+/// Collect matched entries from the parallel walker and print them.
+///
+/// fd walks directories on many threads, but funnels every match through this
+/// single receiver, so all printing — and the output buffer in `output` — runs
+/// on exactly one thread.
+pub fn spawn_receiver(config: &Config, rx: Receiver<DirEntry>, wants_to_quit: Arc<AtomicBool>) {
+    let stdout = std::io::stdout();
+    let mut stdout = stdout.lock();
+
+    for entry in rx {
+        output::print_entry(&mut stdout, &entry, config, &wants_to_quit);
+    }
+
+    // End of batch: drain the buffered output, then close any document-level
+    // framing such as the JSON array's trailing `]`.
+    output::flush(&mut stdout, &wants_to_quit);
+    output::print_epilogue(&mut stdout, config);
+
+    let _ = stdout.flush();
+}